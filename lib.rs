@@ -5,6 +5,7 @@ use ink_lang as ink;
 #[ink::contract]
 mod uke_account_filter {
 
+    use ink_prelude::collections::BTreeMap;
     use ink_prelude::vec::Vec;
     use ink_storage::{traits::SpreadAllocate, Mapping};
 
@@ -17,6 +18,16 @@ mod uke_account_filter {
         status: bool,
     }
 
+    /// Emitted whenever an account is added to or removed from a whitelist.
+    #[ink(event)]
+    pub struct WhitelistChanged {
+        #[ink(topic)]
+        id: AccountId,
+        #[ink(topic)]
+        account: AccountId,
+        added: bool,
+    }
+
     /// Uke Account Filters ink! Smart Contract.  
     /// Used for defining rules related to accounts that message using the uke protocol.
     ///
@@ -33,14 +44,45 @@ mod uke_account_filter {
     pub struct UkeAccountFilter {
         /// Creates a mapping of whether an account is opted in or not.
         opted_in: Mapping<AccountId, bool>,
-        /// If true, it allows all messages.  if false, all messages are deemed as invalid (except those in the whitelist).
-        global_filter: Mapping<AccountId, bool>,
+        /// The account filter policy in effect for each account.
+        filter_mode: Mapping<AccountId, FilterMode>,
         /// Creates a mapping of accounts with privilege to message (whitelist).
-        allowed_accounts: Mapping<AccountId, Vec<AccountId>>,
+        /// Each entry pairs the allowed account with an optional expiry timestamp;
+        /// `None` is a permanent grant, `Some(expiry)` is ignored once expired.
+        allowed_accounts: Mapping<AccountId, Vec<(AccountId, Option<Timestamp>)>>,
+        /// Creates a mapping of accounts explicitly denied from messaging (blocklist).
+        blocked_accounts: Mapping<AccountId, Vec<AccountId>>,
+        /// Members of each group a user has defined, keyed by `(owner, group)`.
+        groups: Mapping<(AccountId, GroupId), Vec<AccountId>>,
+        /// The groups each user has whitelisted.
+        allowed_groups: Mapping<AccountId, Vec<GroupId>>,
+        /// Accounts each owner has authorized to manage their filter settings.
+        delegates: Mapping<AccountId, Vec<AccountId>>,
         /// Default contract address
         default_address: AccountId,
     }
 
+    /// Identifier for a user-defined group of accounts, scoped to its owner.
+    pub type GroupId = u32;
+
+    /// The account filter policy applied to a recipient.
+    #[derive(Debug, PartialEq, Eq, Clone, Copy, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(::scale_info::TypeInfo))]
+    pub enum FilterMode {
+        /// Allow messages from any sender.
+        AllowAll,
+        /// Allow messages only from senders in the whitelist.
+        WhitelistOnly,
+        /// Allow messages from any sender except those in the blocklist.
+        AllowAllExceptBlocked,
+    }
+
+    impl Default for FilterMode {
+        fn default() -> Self {
+            FilterMode::WhitelistOnly
+        }
+    }
+
     /// Errors that can occur upon calling this contract.
     #[derive(Debug, PartialEq, Eq, scale::Encode, scale::Decode)]
     #[cfg_attr(feature = "std", derive(::scale_info::TypeInfo))]
@@ -81,37 +123,296 @@ mod uke_account_filter {
             Ok(())
         }
 
+        /// Grants `delegate` authority to manage `id`'s filter settings.
+        ///
+        /// Only callable by the true owner, so a delegate can never grant
+        /// further delegates or escalate their own access.
+        #[ink(message)]
+        pub fn grant_delegate(&mut self, id: AccountId, delegate: AccountId) -> Result<()> {
+            if !self.is_caller_owner(id) {
+                return Err(Error::CallerIsNotOwner);
+            }
+
+            let mut delegates = self.get_delegates_or_default(id);
+            if !delegates.contains(&delegate) {
+                delegates.push(delegate);
+                self.delegates.insert(&id, &delegates);
+            }
+            Ok(())
+        }
+
+        /// Revokes a delegate's authority to manage `id`'s filter settings.
+        #[ink(message)]
+        pub fn revoke_delegate(&mut self, id: AccountId, delegate: AccountId) -> Result<()> {
+            if !self.is_caller_owner(id) {
+                return Err(Error::CallerIsNotOwner);
+            }
+
+            let mut delegates = self.get_delegates_or_default(id);
+            delegates.retain(|existing| *existing != delegate);
+            self.delegates.insert(&id, &delegates);
+            Ok(())
+        }
+
+        /// Gets the accounts authorized to manage `id`'s filter settings.
+        #[ink(message)]
+        pub fn get_delegates(&self, id: AccountId) -> Vec<AccountId> {
+            self.get_delegates_or_default(id)
+        }
+
         /// Changes global filter for the selected account.
+        ///
+        /// This is a backward-compatible shorthand for [`Self::change_filter_mode`]:
+        /// `true` selects [`FilterMode::AllowAll`], `false` selects [`FilterMode::WhitelistOnly`].
         #[ink(message)]
         pub fn change_global_filter(&mut self, id: AccountId, status: bool) -> Result<()> {
-            if !self.is_caller_owner(id) {
+            let mode = if status {
+                FilterMode::AllowAll
+            } else {
+                FilterMode::WhitelistOnly
+            };
+            self.change_filter_mode(id, mode)
+        }
+
+        /// Gets the global filter status of the selected account.
+        ///
+        /// Returns `true` only when the account's filter mode is [`FilterMode::AllowAll`].
+        #[ink(message)]
+        pub fn get_global_filter(&self, id: AccountId) -> bool {
+            self.get_filter_mode_or_default(id) == FilterMode::AllowAll
+        }
+
+        /// Changes the account filter policy for the selected account.
+        #[ink(message)]
+        pub fn change_filter_mode(&mut self, id: AccountId, mode: FilterMode) -> Result<()> {
+            if !self.is_caller_authorized(id) {
                 return Err(Error::CallerIsNotOwner);
             } else if !self.get_optin_status_or_default(id) {
                 return Err(Error::NotOptedIn);
             }
 
-            self.global_filter.insert(&id, &status);
+            self.filter_mode.insert(&id, &mode);
             Ok(())
         }
 
-        /// Gets the global filter status of the selected account.
+        /// Gets the account filter policy for the selected account.
         #[ink(message)]
-        pub fn get_global_filter(&self, id: AccountId) -> bool {
-            self.get_global_status_or_default(id)
+        pub fn get_filter_mode(&self, id: AccountId) -> FilterMode {
+            self.get_filter_mode_or_default(id)
+        }
+
+        /// Adds an account to the blocklist.
+        #[ink(message)]
+        pub fn add_to_blocked(&mut self, id: AccountId, id_to_block: AccountId) -> Result<()> {
+            if !self.is_caller_authorized(id) {
+                return Err(Error::CallerIsNotOwner);
+            } else if !self.get_optin_status_or_default(id) {
+                return Err(Error::NotOptedIn);
+            }
+
+            let mut vec = self.get_blocked_list_or_default(id);
+            if !vec.contains(&id_to_block) {
+                vec.push(id_to_block);
+                self.blocked_accounts.insert(&id, &vec);
+            }
+            Ok(())
+        }
+
+        /// Removes an account from the blocklist.
+        #[ink(message)]
+        pub fn remove_from_blocked(
+            &mut self,
+            id: AccountId,
+            id_to_unblock: AccountId,
+        ) -> Result<()> {
+            if !self.is_caller_authorized(id) {
+                return Err(Error::CallerIsNotOwner);
+            } else if !self.get_optin_status_or_default(id) {
+                return Err(Error::NotOptedIn);
+            }
+
+            let mut vec = self.get_blocked_list_or_default(id);
+            vec.retain(|account| *account != id_to_unblock);
+            self.blocked_accounts.insert(&id, &vec);
+            Ok(())
+        }
+
+        /// Gets account blocklist.
+        #[ink(message)]
+        pub fn get_blocked_accounts(&self, id: AccountId) -> Vec<AccountId> {
+            self.get_blocked_list_or_default(id)
+        }
+
+        /// Creates an empty group owned by `id`, if it does not already exist.
+        #[ink(message)]
+        pub fn create_group(&mut self, id: AccountId, group: GroupId) -> Result<()> {
+            if !self.is_caller_authorized(id) {
+                return Err(Error::CallerIsNotOwner);
+            } else if !self.get_optin_status_or_default(id) {
+                return Err(Error::NotOptedIn);
+            }
+
+            if self.groups.get(&(id, group)).is_none() {
+                self.groups.insert(&(id, group), &Vec::new());
+            }
+            Ok(())
+        }
+
+        /// Adds `member` to one of `id`'s groups.
+        #[ink(message)]
+        pub fn add_member_to_group(
+            &mut self,
+            id: AccountId,
+            group: GroupId,
+            member: AccountId,
+        ) -> Result<()> {
+            if !self.is_caller_authorized(id) {
+                return Err(Error::CallerIsNotOwner);
+            } else if !self.get_optin_status_or_default(id) {
+                return Err(Error::NotOptedIn);
+            }
+
+            let mut members = self.get_group_members_or_default(id, group);
+            if !members.contains(&member) {
+                members.push(member);
+                self.groups.insert(&(id, group), &members);
+            }
+            Ok(())
+        }
+
+        /// Gets the members of one of `id`'s groups.
+        #[ink(message)]
+        pub fn get_group_members(&self, id: AccountId, group: GroupId) -> Vec<AccountId> {
+            self.get_group_members_or_default(id, group)
+        }
+
+        /// Whitelists an entire group, so any of its members may message `id`.
+        #[ink(message)]
+        pub fn allow_group(&mut self, id: AccountId, group: GroupId) -> Result<()> {
+            if !self.is_caller_authorized(id) {
+                return Err(Error::CallerIsNotOwner);
+            } else if !self.get_optin_status_or_default(id) {
+                return Err(Error::NotOptedIn);
+            }
+
+            let mut allowed = self.get_allowed_groups_or_default(id);
+            if !allowed.contains(&group) {
+                allowed.push(group);
+                self.allowed_groups.insert(&id, &allowed);
+            }
+            Ok(())
+        }
+
+        /// Revokes a group's whitelist access.
+        #[ink(message)]
+        pub fn disallow_group(&mut self, id: AccountId, group: GroupId) -> Result<()> {
+            if !self.is_caller_authorized(id) {
+                return Err(Error::CallerIsNotOwner);
+            } else if !self.get_optin_status_or_default(id) {
+                return Err(Error::NotOptedIn);
+            }
+
+            let mut allowed = self.get_allowed_groups_or_default(id);
+            allowed.retain(|allowed_group| *allowed_group != group);
+            self.allowed_groups.insert(&id, &allowed);
+            Ok(())
         }
 
-        /// Adds a new user to the whitelist.
+        /// Gets the groups `id` has whitelisted.
+        #[ink(message)]
+        pub fn get_allowed_groups(&self, id: AccountId) -> Vec<GroupId> {
+            self.get_allowed_groups_or_default(id)
+        }
+
+        /// Adds a new user to the whitelist with a permanent grant.
+        ///
+        /// Re-adding an account that is already whitelisted replaces its entry
+        /// rather than creating a duplicate.
         #[ink(message)]
         pub fn add_to_allowed(&mut self, id: AccountId, id_to_add: AccountId) -> Result<()> {
-            if !self.is_caller_owner(id) {
+            if !self.is_caller_authorized(id) {
                 return Err(Error::CallerIsNotOwner);
             } else if !self.get_optin_status_or_default(id) {
                 return Err(Error::NotOptedIn);
             }
 
-            let mut vec = self.get_allowed_list_or_default(id);
-            vec.push(id_to_add);
-            self.allowed_accounts.insert(&id, &vec);
+            self.upsert_allowed(id, id_to_add, None);
+            self.env().emit_event(WhitelistChanged {
+                id,
+                account: id_to_add,
+                added: true,
+            });
+            Ok(())
+        }
+
+        /// Adds a new user to the whitelist with a grant that expires at `expiry`.
+        ///
+        /// `expiry` is compared against [`Self::env`]'s `block_timestamp`; the grant
+        /// still holds at `expiry` and is treated as absent once the block
+        /// timestamp is past it.
+        #[ink(message)]
+        pub fn add_to_allowed_until(
+            &mut self,
+            id: AccountId,
+            id_to_add: AccountId,
+            expiry: Timestamp,
+        ) -> Result<()> {
+            if !self.is_caller_authorized(id) {
+                return Err(Error::CallerIsNotOwner);
+            } else if !self.get_optin_status_or_default(id) {
+                return Err(Error::NotOptedIn);
+            }
+
+            self.upsert_allowed(id, id_to_add, Some(expiry));
+            self.env().emit_event(WhitelistChanged {
+                id,
+                account: id_to_add,
+                added: true,
+            });
+            Ok(())
+        }
+
+        /// Removes a user from the whitelist.
+        #[ink(message)]
+        pub fn remove_from_allowed(
+            &mut self,
+            id: AccountId,
+            id_to_remove: AccountId,
+        ) -> Result<()> {
+            if !self.is_caller_authorized(id) {
+                return Err(Error::CallerIsNotOwner);
+            } else if !self.get_optin_status_or_default(id) {
+                return Err(Error::NotOptedIn);
+            }
+
+            let mut entries = self.get_active_allowed_entries(id);
+            entries.retain(|(account, _)| *account != id_to_remove);
+            self.allowed_accounts.insert(&id, &entries);
+            self.env().emit_event(WhitelistChanged {
+                id,
+                account: id_to_remove,
+                added: false,
+            });
+            Ok(())
+        }
+
+        /// Clears the entire whitelist for the selected account.
+        #[ink(message)]
+        pub fn clear_allowed(&mut self, id: AccountId) -> Result<()> {
+            if !self.is_caller_authorized(id) {
+                return Err(Error::CallerIsNotOwner);
+            } else if !self.get_optin_status_or_default(id) {
+                return Err(Error::NotOptedIn);
+            }
+
+            for (account, _) in self.get_active_allowed_entries(id) {
+                self.env().emit_event(WhitelistChanged {
+                    id,
+                    account,
+                    added: false,
+                });
+            }
+            self.allowed_accounts.insert(&id, &Vec::new());
             Ok(())
         }
 
@@ -121,22 +422,163 @@ mod uke_account_filter {
             self.get_allowed_list_or_default(id)
         }
 
+        /// Determines whether `sender` is permitted to message `recipient`.
+        ///
+        /// An account that has not opted in to filtering imposes no restrictions.
+        /// Otherwise, `recipient`'s filter mode decides: a recipient can always
+        /// message themselves, [`FilterMode::AllowAll`] allows anyone,
+        /// [`FilterMode::WhitelistOnly`] restricts senders to the whitelist, and
+        /// [`FilterMode::AllowAllExceptBlocked`] allows anyone not in the blocklist.
+        #[ink(message)]
+        pub fn can_message(&self, sender: AccountId, recipient: AccountId) -> bool {
+            if sender == recipient {
+                return true;
+            }
+
+            if !self.get_optin_status_or_default(recipient) {
+                return true;
+            }
+
+            match self.get_filter_mode_or_default(recipient) {
+                FilterMode::AllowAll => true,
+                FilterMode::WhitelistOnly => {
+                    self.get_allowed_list_or_default(recipient).contains(&sender)
+                        || self.is_in_allowed_group(sender, recipient)
+                }
+                FilterMode::AllowAllExceptBlocked => {
+                    !self.get_blocked_list_or_default(recipient).contains(&sender)
+                }
+            }
+        }
+
+        /// Evaluates `can_message` for many sender/recipient pairs in one call.
+        ///
+        /// Distinct recipients are only read from storage once, regardless of how
+        /// many pairs reference them, since storage reads dominate the gas cost here.
+        #[ink(message)]
+        pub fn can_message_batch(&self, pairs: Vec<(AccountId, AccountId)>) -> Vec<bool> {
+            let mut cache: BTreeMap<
+                AccountId,
+                (bool, FilterMode, Vec<AccountId>, Vec<AccountId>, Vec<AccountId>),
+            > = BTreeMap::new();
+
+            pairs
+                .into_iter()
+                .map(|(sender, recipient)| {
+                    if sender == recipient {
+                        return true;
+                    }
+
+                    let (
+                        opted_in,
+                        mode,
+                        allowed_accounts,
+                        blocked_accounts,
+                        allowed_group_members,
+                    ) = cache.entry(recipient).or_insert_with(|| {
+                        (
+                            self.get_optin_status_or_default(recipient),
+                            self.get_filter_mode_or_default(recipient),
+                            self.get_allowed_list_or_default(recipient),
+                            self.get_blocked_list_or_default(recipient),
+                            self.get_allowed_groups_or_default(recipient)
+                                .into_iter()
+                                .flat_map(|group| {
+                                    self.get_group_members_or_default(recipient, group)
+                                })
+                                .collect(),
+                        )
+                    });
+
+                    if !*opted_in {
+                        return true;
+                    }
+
+                    match mode {
+                        FilterMode::AllowAll => true,
+                        FilterMode::WhitelistOnly => {
+                            allowed_accounts.contains(&sender)
+                                || allowed_group_members.contains(&sender)
+                        }
+                        FilterMode::AllowAllExceptBlocked => !blocked_accounts.contains(&sender),
+                    }
+                })
+                .collect()
+        }
+
         // Utility functions to ensure safe retrieval of various mappings.
 
         fn get_optin_status_or_default(&self, id: AccountId) -> bool {
             self.opted_in.get(&id).unwrap_or(false)
         }
 
-        fn get_global_status_or_default(&self, id: AccountId) -> bool {
-            self.global_filter.get(&id).unwrap_or(false)
+        fn get_filter_mode_or_default(&self, id: AccountId) -> FilterMode {
+            self.filter_mode.get(&id).unwrap_or_default()
         }
 
         fn is_caller_owner(&self, id: AccountId) -> bool {
             id == self.env().caller()
         }
 
+        /// Whether the caller may manage `id`'s filter settings: the owner
+        /// themselves, or one of their delegates.
+        fn is_caller_authorized(&self, id: AccountId) -> bool {
+            self.is_caller_owner(id)
+                || self.get_delegates_or_default(id).contains(&self.env().caller())
+        }
+
+        fn get_delegates_or_default(&self, id: AccountId) -> Vec<AccountId> {
+            self.delegates.get(&id).unwrap_or(Vec::new())
+        }
+
         fn get_allowed_list_or_default(&self, id: AccountId) -> Vec<AccountId> {
-            self.allowed_accounts.get(&id).unwrap_or(Vec::new())
+            self.get_active_allowed_entries(id)
+                .into_iter()
+                .map(|(account, _)| account)
+                .collect()
+        }
+
+        /// Reads `id`'s whitelist with expired grants pruned, so the entries
+        /// returned here are what gets written back on the next whitelist change.
+        fn get_active_allowed_entries(&self, id: AccountId) -> Vec<(AccountId, Option<Timestamp>)> {
+            let now = self.env().block_timestamp();
+            self.allowed_accounts
+                .get(&id)
+                .unwrap_or(Vec::new())
+                .into_iter()
+                .filter(|(_, expiry)| expiry.map_or(true, |expiry| now <= expiry))
+                .collect()
+        }
+
+        /// Inserts or replaces `account`'s grant in `id`'s whitelist, pruning any
+        /// expired entries along the way.
+        fn upsert_allowed(&mut self, id: AccountId, account: AccountId, expiry: Option<Timestamp>) {
+            let mut entries = self.get_active_allowed_entries(id);
+            entries.retain(|(existing, _)| *existing != account);
+            entries.push((account, expiry));
+            self.allowed_accounts.insert(&id, &entries);
+        }
+
+        fn get_blocked_list_or_default(&self, id: AccountId) -> Vec<AccountId> {
+            self.blocked_accounts.get(&id).unwrap_or(Vec::new())
+        }
+
+        fn get_group_members_or_default(&self, id: AccountId, group: GroupId) -> Vec<AccountId> {
+            self.groups.get(&(id, group)).unwrap_or(Vec::new())
+        }
+
+        fn get_allowed_groups_or_default(&self, id: AccountId) -> Vec<GroupId> {
+            self.allowed_groups.get(&id).unwrap_or(Vec::new())
+        }
+
+        /// Whether `sender` is covered by any group `recipient` has whitelisted.
+        fn is_in_allowed_group(&self, sender: AccountId, recipient: AccountId) -> bool {
+            self.get_allowed_groups_or_default(recipient)
+                .into_iter()
+                .any(|group| {
+                    self.get_group_members_or_default(recipient, group)
+                        .contains(&sender)
+                })
         }
     }
 
@@ -286,5 +728,349 @@ mod uke_account_filter {
             assert_eq!(allowed_list.len(), 3);
             assert_eq!(allowed_list[0], default_accounts.bob);
         }
+
+        #[ink::test]
+        fn can_message_works() {
+            let default_accounts = default_accounts();
+            set_next_caller(default_accounts.alice);
+
+            let mut contract = UkeAccountFilter::new();
+
+            // Alice has not opted in yet, so filtering is disabled for her.
+            assert_eq!(
+                contract.can_message(default_accounts.bob, default_accounts.alice),
+                true
+            );
+
+            contract
+                .change_optin_status(true, default_accounts.alice)
+                .unwrap();
+
+            // Opted in with the global filter off and an empty whitelist denies everyone but self.
+            assert_eq!(
+                contract.can_message(default_accounts.alice, default_accounts.alice),
+                true
+            );
+            assert_eq!(
+                contract.can_message(default_accounts.bob, default_accounts.alice),
+                false
+            );
+
+            contract
+                .add_to_allowed(default_accounts.alice, default_accounts.bob)
+                .unwrap();
+            assert_eq!(
+                contract.can_message(default_accounts.bob, default_accounts.alice),
+                true
+            );
+            assert_eq!(
+                contract.can_message(default_accounts.charlie, default_accounts.alice),
+                false
+            );
+
+            contract
+                .change_global_filter(default_accounts.alice, true)
+                .unwrap();
+            assert_eq!(
+                contract.can_message(default_accounts.charlie, default_accounts.alice),
+                true
+            );
+        }
+
+        #[ink::test]
+        fn can_message_batch_works() {
+            let default_accounts = default_accounts();
+            set_next_caller(default_accounts.alice);
+
+            let mut contract = UkeAccountFilter::new();
+
+            contract
+                .change_optin_status(true, default_accounts.alice)
+                .unwrap();
+            contract
+                .add_to_allowed(default_accounts.alice, default_accounts.bob)
+                .unwrap();
+
+            let results = contract.can_message_batch(ink_prelude::vec![
+                (default_accounts.bob, default_accounts.alice),
+                (default_accounts.charlie, default_accounts.alice),
+                (default_accounts.bob, default_accounts.django),
+            ]);
+
+            assert_eq!(results, [true, false, true]);
+        }
+
+        #[ink::test]
+        fn change_filter_mode_works() {
+            let default_accounts = default_accounts();
+            set_next_caller(default_accounts.alice);
+
+            let mut contract = UkeAccountFilter::new();
+
+            assert_eq!(
+                contract.change_filter_mode(
+                    default_accounts.alice,
+                    FilterMode::AllowAllExceptBlocked
+                ),
+                Err(Error::NotOptedIn)
+            );
+
+            contract
+                .change_optin_status(true, default_accounts.alice)
+                .unwrap();
+
+            assert_eq!(
+                contract.get_filter_mode(default_accounts.alice),
+                FilterMode::WhitelistOnly
+            );
+
+            assert_eq!(
+                contract.change_filter_mode(
+                    default_accounts.alice,
+                    FilterMode::AllowAllExceptBlocked
+                ),
+                Ok(())
+            );
+            assert_eq!(
+                contract.get_filter_mode(default_accounts.alice),
+                FilterMode::AllowAllExceptBlocked
+            );
+
+            // The global filter getter/setter remain a backward-compatible view of the mode.
+            contract
+                .change_global_filter(default_accounts.alice, true)
+                .unwrap();
+            assert_eq!(contract.get_filter_mode(default_accounts.alice), FilterMode::AllowAll);
+            assert_eq!(contract.get_global_filter(default_accounts.alice), true);
+        }
+
+        #[ink::test]
+        fn blocked_accounts_work() {
+            let default_accounts = default_accounts();
+            set_next_caller(default_accounts.alice);
+
+            let mut contract = UkeAccountFilter::new();
+
+            contract
+                .change_optin_status(true, default_accounts.alice)
+                .unwrap();
+            contract
+                .change_filter_mode(default_accounts.alice, FilterMode::AllowAllExceptBlocked)
+                .unwrap();
+
+            assert_eq!(
+                contract.can_message(default_accounts.bob, default_accounts.alice),
+                true
+            );
+
+            contract
+                .add_to_blocked(default_accounts.alice, default_accounts.bob)
+                .unwrap();
+
+            assert_eq!(
+                contract.can_message(default_accounts.bob, default_accounts.alice),
+                false
+            );
+            assert_eq!(
+                contract.get_blocked_accounts(default_accounts.alice),
+                [default_accounts.bob]
+            );
+
+            contract
+                .remove_from_blocked(default_accounts.alice, default_accounts.bob)
+                .unwrap();
+            assert_eq!(
+                contract.can_message(default_accounts.bob, default_accounts.alice),
+                true
+            );
+        }
+
+        #[ink::test]
+        fn add_to_allowed_until_expires() {
+            let default_accounts = default_accounts();
+            set_next_caller(default_accounts.alice);
+
+            let mut contract = UkeAccountFilter::new();
+
+            contract
+                .change_optin_status(true, default_accounts.alice)
+                .unwrap();
+
+            let now = ink_env::block_timestamp::<Environment>();
+            contract
+                .add_to_allowed_until(default_accounts.alice, default_accounts.bob, now + 1)
+                .unwrap();
+
+            assert_eq!(
+                contract.can_message(default_accounts.bob, default_accounts.alice),
+                true
+            );
+
+            ink_env::test::set_block_timestamp::<Environment>(now + 2);
+
+            assert_eq!(
+                contract.get_allowed_accounts(default_accounts.alice),
+                Vec::new()
+            );
+            assert_eq!(
+                contract.can_message(default_accounts.bob, default_accounts.alice),
+                false
+            );
+        }
+
+        #[ink::test]
+        fn add_to_allowed_dedupes() {
+            let default_accounts = default_accounts();
+            set_next_caller(default_accounts.alice);
+
+            let mut contract = UkeAccountFilter::new();
+
+            contract
+                .change_optin_status(true, default_accounts.alice)
+                .unwrap();
+
+            contract
+                .add_to_allowed(default_accounts.alice, default_accounts.bob)
+                .unwrap();
+            contract
+                .add_to_allowed(default_accounts.alice, default_accounts.bob)
+                .unwrap();
+
+            assert_eq!(
+                contract.get_allowed_accounts(default_accounts.alice),
+                [default_accounts.bob]
+            );
+        }
+
+        #[ink::test]
+        fn remove_and_clear_allowed_work() {
+            let default_accounts = default_accounts();
+            set_next_caller(default_accounts.alice);
+
+            let mut contract = UkeAccountFilter::new();
+
+            contract
+                .change_optin_status(true, default_accounts.alice)
+                .unwrap();
+            contract
+                .add_to_allowed(default_accounts.alice, default_accounts.bob)
+                .unwrap();
+            contract
+                .add_to_allowed(default_accounts.alice, default_accounts.charlie)
+                .unwrap();
+
+            assert_eq!(
+                contract.remove_from_allowed(default_accounts.alice, default_accounts.bob),
+                Ok(())
+            );
+            assert_eq!(
+                contract.get_allowed_accounts(default_accounts.alice),
+                [default_accounts.charlie]
+            );
+
+            assert_eq!(
+                contract.remove_from_allowed(default_accounts.bob, default_accounts.charlie),
+                Err(Error::CallerIsNotOwner)
+            );
+
+            assert_eq!(contract.clear_allowed(default_accounts.alice), Ok(()));
+            assert_eq!(
+                contract.get_allowed_accounts(default_accounts.alice),
+                Vec::new()
+            );
+        }
+
+        #[ink::test]
+        fn group_whitelisting_works() {
+            let default_accounts = default_accounts();
+            set_next_caller(default_accounts.alice);
+
+            let mut contract = UkeAccountFilter::new();
+
+            contract
+                .change_optin_status(true, default_accounts.alice)
+                .unwrap();
+
+            let work_contacts: GroupId = 1;
+            contract
+                .create_group(default_accounts.alice, work_contacts)
+                .unwrap();
+            contract
+                .add_member_to_group(default_accounts.alice, work_contacts, default_accounts.bob)
+                .unwrap();
+
+            assert_eq!(
+                contract.can_message(default_accounts.bob, default_accounts.alice),
+                false
+            );
+
+            contract
+                .allow_group(default_accounts.alice, work_contacts)
+                .unwrap();
+            assert_eq!(
+                contract.can_message(default_accounts.bob, default_accounts.alice),
+                true
+            );
+            assert_eq!(
+                contract.can_message(default_accounts.charlie, default_accounts.alice),
+                false
+            );
+
+            contract
+                .disallow_group(default_accounts.alice, work_contacts)
+                .unwrap();
+            assert_eq!(
+                contract.can_message(default_accounts.bob, default_accounts.alice),
+                false
+            );
+        }
+
+        #[ink::test]
+        fn delegates_can_manage_filter_settings() {
+            let default_accounts = default_accounts();
+            set_next_caller(default_accounts.alice);
+
+            let mut contract = UkeAccountFilter::new();
+
+            contract
+                .change_optin_status(true, default_accounts.alice)
+                .unwrap();
+
+            assert_eq!(
+                contract.grant_delegate(default_accounts.alice, default_accounts.bob),
+                Ok(())
+            );
+            assert_eq!(
+                contract.get_delegates(default_accounts.alice),
+                [default_accounts.bob]
+            );
+
+            set_next_caller(default_accounts.bob);
+            assert_eq!(
+                contract.add_to_allowed(default_accounts.alice, default_accounts.charlie),
+                Ok(())
+            );
+
+            // Delegates cannot manage opt-in status or grant further delegates.
+            assert_eq!(
+                contract.change_optin_status(false, default_accounts.alice),
+                Err(Error::CallerIsNotOwner)
+            );
+            assert_eq!(
+                contract.grant_delegate(default_accounts.alice, default_accounts.django),
+                Err(Error::CallerIsNotOwner)
+            );
+
+            set_next_caller(default_accounts.alice);
+            contract
+                .revoke_delegate(default_accounts.alice, default_accounts.bob)
+                .unwrap();
+
+            set_next_caller(default_accounts.bob);
+            assert_eq!(
+                contract.add_to_allowed(default_accounts.alice, default_accounts.django),
+                Err(Error::CallerIsNotOwner)
+            );
+        }
     }
 }